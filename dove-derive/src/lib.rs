@@ -0,0 +1,264 @@
+/*
+ * Copyright 2020, Ulf Lilleengen
+ * License: Apache License 2.0 (see the file LICENSE or http://apache.org/licenses/LICENSE-2.0.html).
+ */
+
+//! Derive macro for described-list AMQP frames.
+//!
+//! AMQP frames are described lists with positional fields, and each one otherwise
+//! needs a hand-written `decode`/encode pair. `#[derive(AmqpDescribed)]` generates
+//! both from the struct definition:
+//!
+//! ```ignore
+//! #[derive(AmqpDescribed)]
+//! #[amqp(descriptor = DESC_ERROR)]
+//! struct ErrorCondition {
+//!     #[amqp(mandatory)]
+//!     condition: String,
+//!     description: Option<String>,
+//!     info: Option<BTreeMap<String, Value>>,
+//! }
+//! ```
+//!
+//! Each field must be one of:
+//! * `#[amqp(mandatory)]` — decoding errors if the positional value is missing;
+//! * `Option<T>` — a missing trailing value decodes to `None`;
+//! * `#[amqp(default)]` — a missing value decodes to `Default::default()`.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields};
+
+#[proc_macro_derive(AmqpDescribed, attributes(amqp))]
+pub fn derive_amqp_described(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let descriptor = match struct_descriptor(&input) {
+        Some(d) => d,
+        None => {
+            return syn::Error::new_spanned(
+                &input,
+                "AmqpDescribed requires #[amqp(descriptor = ...)] on the struct",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named) => &named.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    &input,
+                    "AmqpDescribed only supports structs with named fields",
+                )
+                .to_compile_error()
+                .into();
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(&input, "AmqpDescribed only supports structs")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let mut decode_bindings = Vec::new();
+    let mut decode_assigns = Vec::new();
+    let mut encode_pushes = Vec::new();
+
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let mandatory = field_is_mandatory(field);
+        let missing = format!("missing mandatory field {}", ident);
+
+        // Every field is pulled positionally as an optional; mandatory fields
+        // are then checked to be present.
+        decode_bindings.push(quote! {
+            let mut #ident = None;
+            decoder.decode_optional(&mut #ident)?;
+        });
+
+        if mandatory {
+            decode_assigns.push(quote! {
+                #ident: #ident.ok_or_else(|| crate::error::AmqpError::decode_error(Some(#missing)))?
+            });
+        } else if field_is_option(field) {
+            // The field type is itself `Option<T>`, so the decoded optional is
+            // assigned straight through.
+            decode_assigns.push(quote! { #ident: #ident });
+        } else if field_has_default(field) {
+            // A missing non-optional field falls back to `Default::default()`.
+            decode_assigns.push(quote! { #ident: #ident.unwrap_or_default() });
+        } else {
+            return syn::Error::new_spanned(
+                field,
+                "AmqpDescribed field must be #[amqp(mandatory)], of type Option<T>, or #[amqp(default)]",
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        encode_pushes.push(quote! {
+            list.push(crate::convert::IntoValue::into_value(self.#ident));
+        });
+    }
+
+    let expanded = quote! {
+        impl crate::convert::TryFromValue for #name {
+            fn try_from(value: crate::types::Value) -> crate::error::Result<Self> {
+                if let crate::types::Value::Described(descriptor, mut list) = value {
+                    // Match the descriptor against the expected constant the same
+                    // way the hand-written frame decoders do, rather than relying
+                    // on `Value: PartialEq`.
+                    match *descriptor {
+                        #descriptor => {}
+                        ref other => {
+                            return Err(crate::error::AmqpError::decode_error(Some(
+                                format!("Expected descriptor {:?} but found {:?}", #descriptor, other)
+                                    .as_str(),
+                            )));
+                        }
+                    }
+                    let mut decoder = crate::frame_codec::FrameDecoder::new(&descriptor, &mut list)?;
+                    #(#decode_bindings)*
+                    Ok(#name { #(#decode_assigns),* })
+                } else {
+                    Err(crate::error::AmqpError::decode_error(Some(
+                        "Missing expected descriptor",
+                    )))
+                }
+            }
+        }
+
+        impl #name {
+            pub fn encode(self) -> crate::types::Value {
+                let mut list: std::vec::Vec<crate::types::Value> = std::vec::Vec::new();
+                #(#encode_pushes)*
+                // Trailing nulls carry no information in a described list and are
+                // trimmed per the AMQP list-encoding rules.
+                while let Some(crate::types::Value::Null) = list.last() {
+                    list.pop();
+                }
+                crate::types::Value::Described(
+                    std::boxed::Box::new(#descriptor),
+                    std::boxed::Box::new(crate::types::Value::List(list)),
+                )
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Extract the `#[amqp(descriptor = ...)]` expression from the struct attributes.
+///
+/// The right-hand side is a path to a descriptor constant (e.g. `DESC_ERROR`),
+/// not a literal, so the attribute is parsed as an assignment expression.
+fn struct_descriptor(input: &DeriveInput) -> Option<proc_macro2::TokenStream> {
+    for attr in &input.attrs {
+        if !attr.path.is_ident("amqp") {
+            continue;
+        }
+        if let Ok(Expr::Assign(assign)) = attr.parse_args::<Expr>() {
+            if let Expr::Path(path) = &*assign.left {
+                if path.path.is_ident("descriptor") {
+                    let value = &assign.right;
+                    return Some(quote! { #value });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Whether a field carries `#[amqp(mandatory)]`.
+fn field_is_mandatory(field: &syn::Field) -> bool {
+    for attr in &field.attrs {
+        if !attr.path.is_ident("amqp") {
+            continue;
+        }
+        if let Ok(path) = attr.parse_args::<syn::Path>() {
+            if path.is_ident("mandatory") {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Whether a field carries `#[amqp(default)]`, meaning a missing value decodes
+/// to `Default::default()` rather than erroring.
+fn field_has_default(field: &syn::Field) -> bool {
+    for attr in &field.attrs {
+        if !attr.path.is_ident("amqp") {
+            continue;
+        }
+        if let Ok(path) = attr.parse_args::<syn::Path>() {
+            if path.is_ident("default") {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Whether a field's declared type is spelled `Option<...>`. Non-mandatory
+/// fields that are not `Option` need an explicit `#[amqp(default)]` so the
+/// generated assignment stays type-correct.
+fn field_is_option(field: &syn::Field) -> bool {
+    if let syn::Type::Path(type_path) = &field.ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "Option";
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::{parse_str, Data, Fields};
+
+    fn fields_of(input: &DeriveInput) -> Vec<&syn::Field> {
+        match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(named) => named.named.iter().collect(),
+                _ => panic!("expected named fields"),
+            },
+            _ => panic!("expected struct"),
+        }
+    }
+
+    #[test]
+    fn parses_descriptor_and_field_kinds() {
+        let input: DeriveInput = parse_str(
+            r#"
+            #[amqp(descriptor = DESC_ERROR)]
+            struct ErrorCondition {
+                #[amqp(mandatory)]
+                condition: String,
+                description: Option<String>,
+                #[amqp(default)]
+                info: u32,
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert!(struct_descriptor(&input).is_some());
+
+        let fields = fields_of(&input);
+        assert!(field_is_mandatory(fields[0]));
+
+        assert!(!field_is_mandatory(fields[1]));
+        assert!(field_is_option(fields[1]));
+
+        assert!(field_has_default(fields[2]));
+        assert!(!field_is_option(fields[2]));
+    }
+}
@@ -37,80 +37,142 @@ impl<T: TryFromValue> TryFromValue for Option<T> {
 impl<T: TryFromValue> TryFromValue for Vec<T> {
     fn try_from(value: Value) -> Result<Self> {
         match value {
-            Value::List(v) => {
-                let (results, errors): (Vec<_>, Vec<_>) = v
-                    .into_iter()
-                    .map(|f| T::try_from(f))
-                    .partition(Result::is_ok);
-                if errors.len() > 0 {
-                    return Err(AmqpError::decode_error(Some(
-                        "Error decoding list elements",
-                    )));
-                } else {
-                    return Ok(results.into_iter().map(Result::unwrap).collect());
-                }
+            Value::List(v) => decode_sequence("list", v),
+            Value::Array(v) => decode_sequence("array", v),
+            _ => return Ok(vec![T::try_from(value)?]),
+        }
+    }
+}
+
+// Decode each element in order, short-circuiting on the first failure and
+// propagating the original error annotated with the offending index. Nested
+// sequences compose, so a list-of-lists yields a breadcrumb such as
+// `error decoding list element 2: error decoding list element 0: <inner>`.
+fn decode_sequence<T: TryFromValue>(kind: &str, values: Vec<Value>) -> Result<Vec<T>> {
+    let mut decoded = Vec::with_capacity(values.len());
+    for (index, element) in values.into_iter().enumerate() {
+        match T::try_from(element) {
+            Ok(v) => decoded.push(v),
+            Err(e) => {
+                return Err(AmqpError::decode_error(Some(
+                    format!("error decoding {} element {}: {}", kind, index, e).as_str(),
+                )))
             }
-            Value::Array(v) => {
-                let (results, errors): (Vec<_>, Vec<_>) = v
-                    .into_iter()
-                    .map(|f| T::try_from(f))
-                    .partition(Result::is_ok);
-                if errors.len() > 0 {
-                    return Err(AmqpError::decode_error(Some(
-                        "Error decoding array elements",
-                    )));
-                } else {
-                    return Ok(results.into_iter().map(Result::unwrap).collect());
+        }
+    }
+    Ok(decoded)
+}
+
+// Reduce any integral AMQP variant to an `i128` so that the integer impls can
+// accept a value encoded with a narrower (or differently-signed) wire type than
+// the target, as long as the value actually fits. This is a common
+// interoperability requirement: a peer may encode a small number as `smalluint`
+// where another stack expects `uint`.
+fn value_as_i128(value: &Value) -> Option<i128> {
+    match value {
+        Value::Ubyte(v) => Some(*v as i128),
+        Value::Ushort(v) => Some(*v as i128),
+        Value::Uint(v) => Some(*v as i128),
+        Value::Ulong(v) => Some(*v as i128),
+        Value::Byte(v) => Some(*v as i128),
+        Value::Short(v) => Some(*v as i128),
+        Value::Int(v) => Some(*v as i128),
+        Value::Long(v) => Some(*v as i128),
+        _ => None,
+    }
+}
+
+macro_rules! int_from_value {
+    ($($t:ty),*) => {
+        $(
+            impl TryFromValue for $t {
+                fn try_from(value: Value) -> Result<Self> {
+                    match value_as_i128(&value) {
+                        Some(v) => <$t as core::convert::TryFrom<i128>>::try_from(v).map_err(|_| {
+                            AmqpError::decode_error(Some(
+                                concat!("Value out of range for ", stringify!($t)),
+                            ))
+                        }),
+                        None => Err(AmqpError::decode_error(Some(
+                            concat!("Error converting value to ", stringify!($t)),
+                        ))),
+                    }
                 }
             }
-            _ => return Ok(vec![T::try_from(value)?]),
+        )*
+    };
+}
+
+int_from_value!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl TryFromValue for f32 {
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Float(v) => Ok(v),
+            _ => Err(AmqpError::decode_error(Some(
+                "Error converting value to f32",
+            ))),
         }
     }
 }
 
-impl TryFromValue for u8 {
+impl TryFromValue for f64 {
     fn try_from(value: Value) -> Result<Self> {
         match value {
-            Value::Ubyte(v) => return Ok(v),
-            _ => Err(AmqpError::amqp_error(
-                condition::DECODE_ERROR,
-                Some("Error converting value to u8"),
-            )),
+            Value::Double(v) => Ok(v),
+            Value::Float(v) => Ok(v as f64),
+            _ => Err(AmqpError::decode_error(Some(
+                "Error converting value to f64",
+            ))),
         }
     }
 }
 
-impl TryFromValue for u64 {
+impl TryFromValue for char {
     fn try_from(value: Value) -> Result<Self> {
         match value {
-            Value::Ulong(v) => return Ok(v),
+            Value::Char(v) => Ok(v),
             _ => Err(AmqpError::decode_error(Some(
-                "Error converting value to u64",
+                "Error converting value to char",
             ))),
         }
     }
 }
 
-impl TryFromValue for u32 {
+/// Newtype around the raw bytes of a `Value::Binary`. A bare `Vec<u8>` would
+/// overlap the generic `Vec<T>` sequence impl (since `u8: TryFromValue`), so
+/// binary payloads are decoded through this wrapper instead.
+pub struct Binary(pub Vec<u8>);
+
+impl TryFromValue for Binary {
     fn try_from(value: Value) -> Result<Self> {
         match value {
-            Value::Uint(v) => return Ok(v),
-            _ => Err(AmqpError::amqp_error(
-                condition::DECODE_ERROR,
-                Some("Error converting value to u32"),
-            )),
+            Value::Binary(v) => Ok(Binary(v)),
+            _ => Err(AmqpError::decode_error(Some(
+                "Error converting value to binary",
+            ))),
         }
     }
 }
 
-impl TryFromValue for u16 {
+impl TryFromValue for Timestamp {
     fn try_from(value: Value) -> Result<Self> {
         match value {
-            Value::Ushort(v) => return Ok(v),
-            _ => Err(AmqpError::amqp_error(
-                condition::DECODE_ERROR,
-                Some("Error converting value to u32"),
-            )),
+            Value::Timestamp(v) => Ok(v),
+            _ => Err(AmqpError::decode_error(Some(
+                "Error converting value to timestamp",
+            ))),
+        }
+    }
+}
+
+impl TryFromValue for Uuid {
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Uuid(v) => Ok(v),
+            _ => Err(AmqpError::decode_error(Some(
+                "Error converting value to uuid",
+            ))),
         }
     }
 }
@@ -145,7 +207,20 @@ impl TryFromValue for BTreeMap<String, Value> {
             Value::Map(v) => {
                 let mut m = BTreeMap::new();
                 for (key, value) in v.into_iter() {
-                    m.insert(String::try_from(key)?, value);
+                    // Inline the `String` conversion so the original key is still
+                    // available for the error message without cloning it on the
+                    // common success path.
+                    let key = match key {
+                        Value::String(v) => v,
+                        Value::Symbol(v) => String::from_utf8_lossy(&v[..]).to_string(),
+                        key => {
+                            return Err(AmqpError::decode_error(Some(
+                                format!("error decoding map key {:?}: not a string", key)
+                                    .as_str(),
+                            )))
+                        }
+                    };
+                    m.insert(key, value);
                 }
                 Ok(m)
             }
@@ -181,6 +256,129 @@ impl TryFromValue for Symbol {
     }
 }
 
+// Decode a positional `Value::List`/`Value::Array` straight into a tuple of the
+// matching arity, applying the per-element `TryFromValue` conversions in order.
+// The incoming length must match the tuple arity exactly; this pairs with the
+// described-list derive to destructure small frame payloads without boilerplate.
+macro_rules! tuple_from_value {
+    ($count:expr; $($t:ident),+) => {
+        impl<$($t: TryFromValue),+> TryFromValue for ($($t,)+) {
+            fn try_from(value: Value) -> Result<Self> {
+                let values = match value {
+                    Value::List(v) | Value::Array(v) => v,
+                    _ => {
+                        return Err(AmqpError::decode_error(Some(
+                            "Error converting value to tuple",
+                        )))
+                    }
+                };
+                if values.len() != $count {
+                    return Err(AmqpError::decode_error(Some(
+                        format!(
+                            "Error converting value to tuple: expected {} elements but found {}",
+                            $count,
+                            values.len()
+                        )
+                        .as_str(),
+                    )));
+                }
+                let mut iter = values.into_iter();
+                Ok(($($t::try_from(iter.next().unwrap())?,)+))
+            }
+        }
+    };
+}
+
+tuple_from_value!(1; A);
+tuple_from_value!(2; A, B);
+tuple_from_value!(3; A, B, C);
+tuple_from_value!(4; A, B, C, D);
+tuple_from_value!(5; A, B, C, D, E);
+tuple_from_value!(6; A, B, C, D, E, F);
+tuple_from_value!(7; A, B, C, D, E, F, G);
+tuple_from_value!(8; A, B, C, D, E, F, G, H);
+tuple_from_value!(9; A, B, C, D, E, F, G, H, I);
+tuple_from_value!(10; A, B, C, D, E, F, G, H, I, J);
+tuple_from_value!(11; A, B, C, D, E, F, G, H, I, J, K);
+tuple_from_value!(12; A, B, C, D, E, F, G, H, I, J, K, L);
+
+pub trait IntoValue {
+    fn into_value(self) -> Value;
+}
+
+impl IntoValue for u8 {
+    fn into_value(self) -> Value {
+        Value::Ubyte(self)
+    }
+}
+
+impl IntoValue for u16 {
+    fn into_value(self) -> Value {
+        Value::Ushort(self)
+    }
+}
+
+impl IntoValue for u32 {
+    fn into_value(self) -> Value {
+        Value::Uint(self)
+    }
+}
+
+impl IntoValue for u64 {
+    fn into_value(self) -> Value {
+        Value::Ulong(self)
+    }
+}
+
+impl IntoValue for bool {
+    fn into_value(self) -> Value {
+        Value::Bool(self)
+    }
+}
+
+impl IntoValue for String {
+    fn into_value(self) -> Value {
+        Value::String(self)
+    }
+}
+
+impl IntoValue for Symbol {
+    fn into_value(self) -> Value {
+        Value::Symbol(self.to_vec())
+    }
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+    fn into_value(self) -> Value {
+        match self {
+            Some(v) => v.into_value(),
+            None => Value::Null,
+        }
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self) -> Value {
+        Value::List(self.into_iter().map(IntoValue::into_value).collect())
+    }
+}
+
+impl IntoValue for BTreeMap<String, Value> {
+    fn into_value(self) -> Value {
+        Value::Map(
+            self.into_iter()
+                .map(|(k, v)| (k.into_value(), v))
+                .collect(),
+        )
+    }
+}
+
+impl IntoValue for BTreeMap<Value, Value> {
+    fn into_value(self) -> Value {
+        Value::Map(self)
+    }
+}
+
 impl TryFromValue for ErrorCondition {
     fn try_from(value: Value) -> Result<Self> {
         if let Value::Described(descriptor, mut list) = value {
@@ -198,3 +396,90 @@ impl TryFromValue for ErrorCondition {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbol_round_trips_through_value() {
+        let symbol = Symbol::from_vec(b"amqp:link:detach-forced".to_vec());
+        let value = symbol.clone().into_value();
+        assert_eq!(value, Value::Symbol(b"amqp:link:detach-forced".to_vec()));
+        assert_eq!(Symbol::try_from(value).unwrap(), symbol);
+    }
+
+    #[test]
+    fn list_decode_reports_failing_element_index() {
+        let value = Value::List(vec![Value::Uint(1), Value::Bool(true)]);
+        let err = Vec::<u32>::try_from(value).unwrap_err();
+        assert!(
+            err.to_string().contains("error decoding list element 1"),
+            "unexpected message: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn nested_list_decode_composes_breadcrumb() {
+        let value = Value::List(vec![
+            Value::List(vec![Value::Uint(1)]),
+            Value::List(vec![Value::Bool(true)]),
+        ]);
+        let err = Vec::<Vec<u32>>::try_from(value).unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.contains("error decoding list element 1")
+                && message.contains("error decoding list element 0"),
+            "unexpected message: {}",
+            message
+        );
+    }
+
+    #[test]
+    fn map_decode_reports_offending_key() {
+        let mut map = BTreeMap::new();
+        map.insert(Value::Uint(7), Value::Bool(true));
+        let err = BTreeMap::<String, Value>::try_from(Value::Map(map)).unwrap_err();
+        assert!(
+            err.to_string().contains("error decoding map key"),
+            "unexpected message: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn integer_accepts_narrower_variant() {
+        assert_eq!(u32::try_from(Value::Ubyte(200)).unwrap(), 200);
+        assert_eq!(i16::try_from(Value::Byte(-5)).unwrap(), -5);
+    }
+
+    #[test]
+    fn integer_rejects_out_of_range_value() {
+        assert!(u8::try_from(Value::Uint(300)).is_err());
+        assert!(i8::try_from(Value::Uint(200)).is_err());
+    }
+
+    #[test]
+    fn binary_decodes_through_newtype() {
+        let Binary(bytes) = Binary::try_from(Value::Binary(vec![1, 2, 3])).unwrap();
+        assert_eq!(bytes, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn tuple_decodes_positionally() {
+        let value = Value::List(vec![
+            Value::Ubyte(7),
+            Value::String("queue".to_string()),
+            Value::Bool(true),
+        ]);
+        let decoded = <(u8, String, bool)>::try_from(value).unwrap();
+        assert_eq!(decoded, (7, "queue".to_string(), true));
+    }
+
+    #[test]
+    fn tuple_rejects_arity_mismatch() {
+        let value = Value::List(vec![Value::Ubyte(7), Value::Bool(true)]);
+        assert!(<(u8, String, bool)>::try_from(value).is_err());
+    }
+}
@@ -0,0 +1,276 @@
+/*
+ * Copyright 2020, Ulf Lilleengen
+ * License: Apache License 2.0 (see the file LICENSE or http://apache.org/licenses/LICENSE-2.0.html).
+ */
+
+use std::collections::btree_map;
+use std::fmt;
+use std::vec;
+
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::error::*;
+use crate::types::*;
+
+/**
+ *************************************************************************************
+ * A `serde::Deserializer` adapter over `Value`. Alongside the hand-written         *
+ * `TryFromValue` impls used for frame decoding, it lets any `serde::Deserialize`   *
+ * type be populated straight from an AMQP body or application-properties map.      *
+ * The typed unsigned entry points (`deserialize_u8` .. `deserialize_u64`) accept   *
+ * only their exact wire variant; unlike `TryFromValue`, they do not widen a        *
+ * narrower integer variant.                                                        *
+ *************************************************************************************
+ */
+
+impl de::Error for AmqpError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        AmqpError::decode_error(Some(msg.to_string().as_str()))
+    }
+}
+
+/// Deserializer wrapping a single decoded AMQP [`Value`].
+pub struct ValueDeserializer {
+    pub value: Value,
+}
+
+impl ValueDeserializer {
+    pub fn new(value: Value) -> ValueDeserializer {
+        ValueDeserializer { value }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
+    type Error = AmqpError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::Ubyte(v) => visitor.visit_u8(v),
+            Value::Ushort(v) => visitor.visit_u16(v),
+            Value::Uint(v) => visitor.visit_u32(v),
+            Value::Ulong(v) => visitor.visit_u64(v),
+            Value::Byte(v) => visitor.visit_i8(v),
+            Value::Short(v) => visitor.visit_i16(v),
+            Value::Int(v) => visitor.visit_i32(v),
+            Value::Long(v) => visitor.visit_i64(v),
+            Value::Float(v) => visitor.visit_f32(v),
+            Value::Double(v) => visitor.visit_f64(v),
+            Value::Char(v) => visitor.visit_char(v),
+            Value::Binary(v) => visitor.visit_byte_buf(v),
+            Value::String(v) => visitor.visit_str(&v),
+            Value::Symbol(v) => visitor.visit_str(&String::from_utf8_lossy(&v[..])),
+            Value::Map(v) => visitor.visit_map(MapDeserializer::new(v)),
+            Value::List(v) => visitor.visit_seq(SeqDeserializer::new(v)),
+            Value::Array(v) => visitor.visit_seq(SeqDeserializer::new(v)),
+            _ => Err(AmqpError::decode_error(Some(
+                "Unsupported value for serde deserialization",
+            ))),
+        }
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Ubyte(v) => visitor.visit_u8(v),
+            _ => Err(AmqpError::decode_error(Some("Error converting value to u8"))),
+        }
+    }
+
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Ushort(v) => visitor.visit_u16(v),
+            _ => Err(AmqpError::decode_error(Some(
+                "Error converting value to u16",
+            ))),
+        }
+    }
+
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Uint(v) => visitor.visit_u32(v),
+            _ => Err(AmqpError::decode_error(Some(
+                "Error converting value to u32",
+            ))),
+        }
+    }
+
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Ulong(v) => visitor.visit_u64(v),
+            _ => Err(AmqpError::decode_error(Some(
+                "Error converting value to u64",
+            ))),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Bool(v) => visitor.visit_bool(v),
+            _ => Err(AmqpError::decode_error(Some(
+                "Error converting value to bool",
+            ))),
+        }
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::String(v) => visitor.visit_str(&v),
+            Value::Symbol(v) => visitor.visit_str(&String::from_utf8_lossy(&v[..])),
+            _ => Err(AmqpError::decode_error(Some(
+                "Error converting value to String",
+            ))),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 u128 i128 f32 f64 char string bytes byte_buf unit
+        unit_struct newtype_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+/// `SeqAccess` yielding a [`ValueDeserializer`] per element of a list or array.
+struct SeqDeserializer {
+    iter: vec::IntoIter<Value>,
+}
+
+impl SeqDeserializer {
+    fn new(values: Vec<Value>) -> SeqDeserializer {
+        SeqDeserializer {
+            iter: values.into_iter(),
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer {
+    type Error = AmqpError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some(value) => seed.deserialize(ValueDeserializer::new(value)).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// `MapAccess` yielding key/value [`ValueDeserializer`]s for each map entry.
+struct MapDeserializer {
+    iter: btree_map::IntoIter<Value, Value>,
+    value: Option<Value>,
+}
+
+impl MapDeserializer {
+    fn new(values: std::collections::BTreeMap<Value, Value>) -> MapDeserializer {
+        MapDeserializer {
+            iter: values.into_iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer {
+    type Error = AmqpError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(ValueDeserializer::new(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self.value.take().ok_or_else(|| {
+            AmqpError::decode_error(Some("Map value requested before key"))
+        })?;
+        seed.deserialize(ValueDeserializer::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Props {
+        name: String,
+        count: u32,
+        // A signed field exercises the `Value::Int` branch added to
+        // `deserialize_any`.
+        delta: i32,
+        enabled: bool,
+    }
+
+    #[test]
+    fn deserialize_struct_from_map() {
+        let mut map = BTreeMap::new();
+        map.insert(Value::String("name".to_string()), Value::String("queue".to_string()));
+        map.insert(Value::String("count".to_string()), Value::Uint(3));
+        map.insert(Value::String("delta".to_string()), Value::Int(-1));
+        map.insert(Value::String("enabled".to_string()), Value::Bool(true));
+
+        let props = Props::deserialize(ValueDeserializer::new(Value::Map(map))).unwrap();
+        assert_eq!(
+            props,
+            Props {
+                name: "queue".to_string(),
+                count: 3,
+                delta: -1,
+                enabled: true,
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_seq_from_list() {
+        let list = Value::List(vec![Value::Uint(1), Value::Uint(2), Value::Uint(3)]);
+        let decoded = Vec::<u32>::deserialize(ValueDeserializer::new(list)).unwrap();
+        assert_eq!(decoded, vec![1, 2, 3]);
+    }
+}